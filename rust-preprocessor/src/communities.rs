@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::entities::Entity;
+
+/// A detected community: a set of entities that are densely connected to each other
+#[derive(Debug, Clone)]
+pub struct Community {
+    /// Community identifier (stable for a given propagation run)
+    pub id: usize,
+
+    /// Names of the entities belonging to this community
+    pub members: Vec<String>,
+}
+
+/// Maximum number of label propagation passes before giving up on convergence
+const MAX_ITERATIONS: usize = 100;
+
+/// Run label propagation over the undirected neighbor graph implied by `Entity::related`.
+///
+/// Returns a label per entity, indexed the same way as `entities`. Labels start out as each
+/// entity's own index and converge as neighbors adopt the plurality label among their
+/// neighbors, breaking ties deterministically by preferring the lowest label id.
+pub fn label_propagation(entities: &[Entity]) -> Vec<usize> {
+    let name_to_index: HashMap<&str, usize> = entities
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.name.as_str(), i))
+        .collect();
+
+    // Build an undirected adjacency list from the (directed) `related` edges
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); entities.len()];
+    for (i, entity) in entities.iter().enumerate() {
+        for related_name in &entity.related {
+            if let Some(&j) = name_to_index.get(related_name.as_str()) {
+                if j != i {
+                    adjacency[i].push(j);
+                    adjacency[j].push(i);
+                }
+            }
+        }
+    }
+
+    let mut labels: Vec<usize> = (0..entities.len()).collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+
+        for i in 0..entities.len() {
+            if adjacency[i].is_empty() {
+                continue;
+            }
+
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for &neighbor in &adjacency[i] {
+                *counts.entry(labels[neighbor]).or_insert(0) += 1;
+            }
+
+            // Pick the plurality label, breaking ties by the lowest label id
+            let mut ranked: Vec<(usize, usize)> = counts.into_iter().collect();
+            ranked.sort_by_key(|&(label, _)| label);
+
+            let mut best_label = labels[i];
+            let mut best_count = 0;
+            for (label, count) in ranked {
+                if count > best_count {
+                    best_count = count;
+                    best_label = label;
+                }
+            }
+
+            if best_label != labels[i] {
+                labels[i] = best_label;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    labels
+}
+
+/// Group entities into `Community` records given the labels produced by `label_propagation`
+pub fn build_communities(entities: &[Entity], labels: &[usize]) -> Vec<Community> {
+    let mut groups: HashMap<usize, Vec<String>> = HashMap::new();
+
+    for (i, &label) in labels.iter().enumerate() {
+        groups.entry(label).or_default().push(entities[i].name.clone());
+    }
+
+    let mut communities: Vec<Community> = groups
+        .into_iter()
+        .map(|(id, members)| Community { id, members })
+        .collect();
+
+    communities.sort_by_key(|c| c.id);
+    communities
+}
+
+/// Detect communities among the entity set in one call
+pub fn detect_communities(entities: &[Entity]) -> (Vec<usize>, Vec<Community>) {
+    let labels = label_propagation(entities);
+    let communities = build_communities(entities, &labels);
+    (labels, communities)
+}
@@ -1,6 +1,7 @@
-use anyhow::{Result, Context};
-use regex::Regex;
+use anyhow::Result;
+use regex::{Regex, RegexSet};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Processed document data
 #[derive(Debug, Clone)]
@@ -16,6 +17,9 @@ pub struct ProcessedDocument {
 
     /// Extracted key-value pairs
     pub metadata: HashMap<String, String>,
+
+    /// Normative (RFC 2119-style) obligations found in section content
+    pub requirements: Vec<Requirement>,
 }
 
 /// Document section
@@ -31,6 +35,37 @@ pub struct Section {
     pub level: usize,
 }
 
+/// Strength of a normative (RFC 2119) obligation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    May,
+    Should,
+    Must,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Must => write!(f, "Must"),
+            Severity::Should => write!(f, "Should"),
+            Severity::May => write!(f, "May"),
+        }
+    }
+}
+
+/// A single normative obligation found in a section
+#[derive(Debug, Clone)]
+pub struct Requirement {
+    /// Title of the section the obligation was found in
+    pub section_title: Option<String>,
+
+    /// The sentence containing the obligation
+    pub sentence: String,
+
+    /// The highest severity keyword found in the sentence
+    pub severity: Severity,
+}
+
 /// Process a document and return structured data
 pub fn process(content: &str, doc_type: &str) -> Result<ProcessedDocument> {
     // Create a new processed document
@@ -39,6 +74,7 @@ pub fn process(content: &str, doc_type: &str) -> Result<ProcessedDocument> {
         title: None,
         sections: Vec::new(),
         metadata: HashMap::new(),
+        requirements: Vec::new(),
     };
 
     // Extract document title
@@ -50,6 +86,9 @@ pub fn process(content: &str, doc_type: &str) -> Result<ProcessedDocument> {
     // Extract metadata
     doc.metadata = extract_metadata(content, doc_type)?;
 
+    // Extract normative obligations (RFC 2119-style keywords)
+    doc.requirements = extract_requirements(&doc.sections);
+
     Ok(doc)
 }
 
@@ -162,43 +201,79 @@ fn extract_sections(content: &str) -> Result<Vec<Section>> {
     Ok(sections)
 }
 
+/// Raw metadata patterns for a single document type, paired with the RegexSet
+/// built from the same pattern strings so callers can cheaply learn which
+/// patterns can possibly match before running the individual regexes.
+struct MetadataPatternTable {
+    set: RegexSet,
+    patterns: Vec<(Regex, &'static str)>,
+}
+
+/// Metadata patterns, keyed by document type, compiled exactly once
+fn metadata_pattern_table(doc_type: &str) -> &'static MetadataPatternTable {
+    static PLAN: OnceLock<MetadataPatternTable> = OnceLock::new();
+    static POLICY: OnceLock<MetadataPatternTable> = OnceLock::new();
+    static CLAIM: OnceLock<MetadataPatternTable> = OnceLock::new();
+    static GENERIC: OnceLock<MetadataPatternTable> = OnceLock::new();
+
+    fn build(raw: &[(&'static str, &'static str)]) -> MetadataPatternTable {
+        let set = RegexSet::new(raw.iter().map(|(pattern, _)| *pattern))
+            .expect("metadata patterns should be valid regexes");
+
+        let patterns = raw
+            .iter()
+            .map(|(pattern, key)| (Regex::new(pattern).unwrap(), *key))
+            .collect();
+
+        MetadataPatternTable { set, patterns }
+    }
+
+    match doc_type {
+        "plan" => PLAN.get_or_init(|| {
+            build(&[
+                (r"(?i)Plan\s+ID\s*:\s*([A-Z0-9-]+)", "plan_id"),
+                (r"(?i)Effective\s+Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "effective_date"),
+                (r"(?i)Coverage\s+Type\s*:\s*([A-Za-z\s]+)", "coverage_type"),
+                (r"(?i)Premium\s*:\s*\$?(\d+(?:\.\d{2})?)", "premium"),
+            ])
+        }),
+        "policy" => POLICY.get_or_init(|| {
+            build(&[
+                (r"(?i)Policy\s+Number\s*:\s*([A-Z0-9-]+)", "policy_number"),
+                (r"(?i)Policyholder\s*:\s*([A-Za-z\s]+)", "policyholder"),
+                (r"(?i)Issue\s+Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "issue_date"),
+                (r"(?i)Expiration\s+Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "expiration_date"),
+            ])
+        }),
+        "claim" => CLAIM.get_or_init(|| {
+            build(&[
+                (r"(?i)Claim\s+Number\s*:\s*([A-Z0-9-]+)", "claim_number"),
+                (r"(?i)Date\s+of\s+Service\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "service_date"),
+                (r"(?i)Provider\s*:\s*([A-Za-z\s]+)", "provider"),
+                (r"(?i)Amount\s*:\s*\$?(\d+(?:\.\d{2})?)", "amount"),
+                (r"(?i)Status\s*:\s*([A-Za-z\s]+)", "status"),
+            ])
+        }),
+        _ => GENERIC.get_or_init(|| {
+            build(&[
+                // Generic patterns for any document type
+                (r"(?i)ID\s*:\s*([A-Z0-9-]+)", "id"),
+                (r"(?i)Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "date"),
+                (r"(?i)Name\s*:\s*([A-Za-z\s]+)", "name"),
+            ])
+        }),
+    }
+}
+
 /// Extract metadata from the document
 fn extract_metadata(content: &str, doc_type: &str) -> Result<HashMap<String, String>> {
     let mut metadata = HashMap::new();
+    let table = metadata_pattern_table(doc_type);
 
-    // Define patterns based on document type
-    let patterns: Vec<(&str, &str)> = match doc_type {
-        "plan" => vec![
-            (r"(?i)Plan\s+ID\s*:\s*([A-Z0-9-]+)", "plan_id"),
-            (r"(?i)Effective\s+Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "effective_date"),
-            (r"(?i)Coverage\s+Type\s*:\s*([A-Za-z\s]+)", "coverage_type"),
-            (r"(?i)Premium\s*:\s*\$?(\d+(?:\.\d{2})?)", "premium"),
-        ],
-        "policy" => vec![
-            (r"(?i)Policy\s+Number\s*:\s*([A-Z0-9-]+)", "policy_number"),
-            (r"(?i)Policyholder\s*:\s*([A-Za-z\s]+)", "policyholder"),
-            (r"(?i)Issue\s+Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "issue_date"),
-            (r"(?i)Expiration\s+Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "expiration_date"),
-        ],
-        "claim" => vec![
-            (r"(?i)Claim\s+Number\s*:\s*([A-Z0-9-]+)", "claim_number"),
-            (r"(?i)Date\s+of\s+Service\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "service_date"),
-            (r"(?i)Provider\s*:\s*([A-Za-z\s]+)", "provider"),
-            (r"(?i)Amount\s*:\s*\$?(\d+(?:\.\d{2})?)", "amount"),
-            (r"(?i)Status\s*:\s*([A-Za-z\s]+)", "status"),
-        ],
-        _ => vec![
-            // Generic patterns for any document type
-            (r"(?i)ID\s*:\s*([A-Z0-9-]+)", "id"),
-            (r"(?i)Date\s*:\s*(\d{1,2}/\d{1,2}/\d{2,4})", "date"),
-            (r"(?i)Name\s*:\s*([A-Za-z\s]+)", "name"),
-        ],
-    };
-
-    // Extract metadata using regex patterns
-    for (pattern, key) in patterns {
-        let re = Regex::new(pattern)
-            .with_context(|| format!("Failed to compile regex pattern: {}", pattern))?;
+    // Prefilter with the RegexSet so only patterns that can possibly match
+    // ever run their (more expensive) capturing regex.
+    for idx in table.set.matches(content).into_iter() {
+        let (re, key) = &table.patterns[idx];
 
         if let Some(captures) = re.captures(content) {
             if captures.len() > 1 {
@@ -209,3 +284,91 @@ fn extract_metadata(content: &str, doc_type: &str) -> Result<HashMap<String, Str
 
     Ok(metadata)
 }
+
+/// Table of normative keyword patterns, each paired with the severity it implies. Patterns that
+/// allow a negation (a "NOT" within the modal verb) capture it in their first group.
+fn obligation_patterns() -> &'static (RegexSet, Vec<(Regex, Severity)>) {
+    static PATTERNS: OnceLock<(RegexSet, Vec<(Regex, Severity)>)> = OnceLock::new();
+
+    PATTERNS.get_or_init(|| {
+        let raw: &[(&str, Severity)] = &[
+            (r"\bMUST( NOT)?\b", Severity::Must),
+            (r"\bSHALL( NOT)?\b", Severity::Must),
+            (r"\bREQUIRED\b", Severity::Must),
+            (r"\bSHOULD( NOT)?\b", Severity::Should),
+            (r"\b(NOT )?RECOMMENDED\b", Severity::Should),
+            (r"\bMAY\b", Severity::May),
+            (r"\bOPTIONAL\b", Severity::May),
+        ];
+
+        let set = RegexSet::new(raw.iter().map(|(pattern, _)| pattern))
+            .expect("obligation keyword patterns should be valid regexes");
+
+        let compiled = raw
+            .iter()
+            .map(|(pattern, severity)| (Regex::new(pattern).unwrap(), *severity))
+            .collect();
+
+        (set, compiled)
+    })
+}
+
+/// Find the strongest RFC 2119-style obligation keyword in `text`, if any, along with whether
+/// a negation ("NOT") accompanied it. Shared by `extract_requirements` below and by
+/// `entities::tag_obligation`, so the keyword table only needs to be maintained in one place.
+pub(crate) fn classify_obligation(text: &str) -> Option<(Severity, bool)> {
+    let (set, patterns) = obligation_patterns();
+
+    let matched: Vec<usize> = set.matches(text).into_iter().collect();
+    if matched.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(Severity, bool)> = None;
+
+    for idx in matched {
+        let (pattern, severity) = &patterns[idx];
+        if let Some(captures) = pattern.captures(text) {
+            let negated = captures.get(1).is_some();
+
+            best = match best {
+                Some((best_severity, best_negated)) if best_severity >= *severity => {
+                    Some((best_severity, best_negated))
+                }
+                _ => Some((*severity, negated)),
+            };
+        }
+    }
+
+    best
+}
+
+/// Split section content into sentences for obligation scanning
+fn split_sentences(content: &str) -> Vec<&str> {
+    content
+        .split(['.', '\n'])
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Scan every section for RFC 2119-style normative obligations
+fn extract_requirements(sections: &[Section]) -> Vec<Requirement> {
+    let mut requirements = Vec::new();
+
+    for section in sections {
+        for sentence in split_sentences(&section.content) {
+            let Some((severity, _negated)) = classify_obligation(sentence) else {
+                continue;
+            };
+
+            requirements.push(Requirement {
+                section_title: section.title.clone(),
+                sentence: sentence.to_string(),
+                severity,
+            });
+        }
+    }
+
+    requirements
+}
@@ -1,10 +1,12 @@
 use anyhow::Result;
 use regex::Regex;
-use aho_corasick::AhoCorasick;
+use aho_corasick::{AhoCorasickBuilder, MatchKind};
 use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
 
-use crate::document::ProcessedDocument;
+use crate::document::{classify_obligation, ProcessedDocument};
+use crate::rules::MatchingRule;
+use crate::zoning::SectionMatcher;
 
 /// Entity types for healthcare insurance documents
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +23,35 @@ pub enum EntityType {
     Limitation,
 }
 
+/// A byte-offset location within a document section, used to trace an entity back to its source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    /// Index into `ProcessedDocument::sections`
+    pub section_index: usize,
+
+    /// Start byte offset within the section's content
+    pub start: usize,
+
+    /// End byte offset within the section's content
+    pub end: usize,
+}
+
+/// Severity of a diagnostic raised during extraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// A machine-readable signal about ambiguous or conflicting extraction results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    pub span: Option<SourceSpan>,
+    pub code: String,
+    pub message: String,
+}
+
 /// Entity extracted from a document
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Entity {
@@ -38,33 +69,91 @@ pub struct Entity {
 
     /// Additional attributes
     pub attributes: HashMap<String, String>,
+
+    /// Location in the source document this entity was extracted from, when known
+    pub span: Option<SourceSpan>,
 }
 
-/// Extract entities from a processed document
-pub fn extract(doc: &ProcessedDocument, doc_type: &str) -> Result<Vec<Entity>> {
+/// Extract entities from a processed document, optionally driving section classification from
+/// externally configured zoning matchers instead of the built-in keyword heuristics, and
+/// optionally running a set of declarative matcher rules (see [`crate::rules`]) for `doc_type`
+/// over every section. Alongside the entities, returns diagnostics surfacing ambiguous or
+/// conflicting extraction results.
+pub fn extract_with_zoning(
+    doc: &ProcessedDocument,
+    doc_type: &str,
+    zoning: Option<&[SectionMatcher]>,
+    rules: Option<&[MatchingRule]>,
+) -> Result<(Vec<Entity>, Vec<Diagnostic>)> {
     let mut entities = Vec::new();
+    let mut diagnostics = Vec::new();
 
     // Extract entities based on document type
     match doc_type {
-        "plan" => extract_plan_entities(doc, &mut entities)?,
-        "policy" => extract_policy_entities(doc, &mut entities)?,
+        "plan" => extract_plan_entities(doc, &mut entities, zoning)?,
+        "policy" => extract_policy_entities(doc, &mut entities, zoning)?,
         "claim" => extract_claim_entities(doc, &mut entities)?,
         _ => extract_generic_entities(doc, &mut entities)?,
     }
 
     // Extract common entities across all document types
-    extract_benefits(doc, &mut entities)?;
-    extract_exclusions(doc, &mut entities)?;
+    if let Some(matchers) = zoning {
+        extract_by_zoning(doc, matchers, &mut entities)?;
+    } else {
+        extract_benefits(doc, &mut entities, &mut diagnostics)?;
+        extract_exclusions(doc, &mut entities, &mut diagnostics)?;
+    }
     extract_procedures(doc, &mut entities)?;
 
+    // Surface the document-level obligation scan as entities so it flows through the same
+    // JSON/CSV/pivot output path as everything else
+    extract_document_requirements(doc, &mut entities)?;
+
+    // Run any configured matcher rules for this document type (currency limits, copay
+    // percentages, waiting periods, and anything else a config can express)
+    if let Some(rule_list) = rules {
+        crate::rules::apply_rules(doc, rule_list, &mut entities)?;
+    }
+
     // Deduplicate entities
     deduplicate_entities(&mut entities);
 
-    Ok(entities)
+    // Flag cross-entity conflicts now that the full set is known
+    diagnostics.extend(find_conflicts(&entities));
+
+    Ok((entities, diagnostics))
+}
+
+/// Classify sections using externally configured heading matchers instead of hardcoded
+/// keyword arrays. Each section is assigned to the first matcher whose heading pattern matches
+/// its title or content, producing an entity of that matcher's configured `EntityType`.
+fn extract_by_zoning(doc: &ProcessedDocument, matchers: &[SectionMatcher], entities: &mut Vec<Entity>) -> Result<()> {
+    for section in &doc.sections {
+        let heading = section.title.clone().unwrap_or_default();
+
+        for matcher in matchers {
+            if matcher.regex.is_match(&heading) || matcher.regex.is_match(&section.content) {
+                let mut entity = Entity {
+                    entity_type: matcher.entity_type.clone(),
+                    name: section.title.clone().unwrap_or_else(|| matcher.name.clone()),
+                    description: Some(section.content.clone()),
+                    related: Vec::new(),
+                    attributes: HashMap::new(),
+                    span: None,
+                };
+                tag_obligation(&mut entity, &section.content);
+
+                entities.push(entity);
+                break;
+            }
+        }
+    }
+
+    Ok(())
 }
 
 /// Extract plan-specific entities
-fn extract_plan_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Result<()> {
+fn extract_plan_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>, zoning: Option<&[SectionMatcher]>) -> Result<()> {
     // Extract plan entity
     let mut plan_entity = Entity {
         entity_type: "Plan".to_string(),
@@ -72,6 +161,7 @@ fn extract_plan_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>) ->
         description: None,
         related: Vec::new(),
         attributes: HashMap::new(),
+        span: None,
     };
 
     // Add metadata as attributes
@@ -93,14 +183,16 @@ fn extract_plan_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>) ->
 
     entities.push(plan_entity);
 
-    // Extract coverage entities
-    extract_coverage(doc, entities)?;
+    // Extract coverage entities (superseded by zoning matchers when configured)
+    if zoning.is_none() {
+        extract_coverage(doc, entities)?;
+    }
 
     Ok(())
 }
 
 /// Extract policy-specific entities
-fn extract_policy_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Result<()> {
+fn extract_policy_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>, zoning: Option<&[SectionMatcher]>) -> Result<()> {
     // Extract policy entity
     let mut policy_entity = Entity {
         entity_type: "Policy".to_string(),
@@ -108,6 +200,7 @@ fn extract_policy_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>)
         description: None,
         related: Vec::new(),
         attributes: HashMap::new(),
+        span: None,
     };
 
     // Add metadata as attributes
@@ -129,8 +222,10 @@ fn extract_policy_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>)
 
     entities.push(policy_entity);
 
-    // Extract conditions and limitations
-    extract_conditions_and_limitations(doc, entities)?;
+    // Extract conditions and limitations (superseded by zoning matchers when configured)
+    if zoning.is_none() {
+        extract_conditions_and_limitations(doc, entities)?;
+    }
 
     Ok(())
 }
@@ -146,6 +241,7 @@ fn extract_claim_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -
         description: None,
         related: Vec::new(),
         attributes: HashMap::new(),
+        span: None,
     };
 
     // Add metadata as attributes
@@ -165,6 +261,7 @@ fn extract_claim_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -
             description: None,
             related: vec![claim_name],
             attributes: HashMap::new(),
+            span: None,
         };
 
         entities.push(provider_entity);
@@ -187,6 +284,7 @@ fn extract_generic_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>)
         description: None,
         related: Vec::new(),
         attributes: HashMap::new(),
+        span: None,
     };
 
     // Add metadata as attributes
@@ -200,14 +298,14 @@ fn extract_generic_entities(doc: &ProcessedDocument, entities: &mut Vec<Entity>)
 }
 
 /// Extract benefits from the document
-fn extract_benefits(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Result<()> {
+fn extract_benefits(doc: &ProcessedDocument, entities: &mut Vec<Entity>, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
     // Keywords that indicate benefit sections
     let benefit_keywords = [
         "benefit", "benefits", "covered", "coverage", "covers", "included", "includes"
     ];
 
     // Find sections related to benefits
-    for section in &doc.sections {
+    for (section_index, section) in doc.sections.iter().enumerate() {
         let section_text = if let Some(title) = &section.title {
             format!("{} {}", title, section.content)
         } else {
@@ -230,14 +328,21 @@ fn extract_benefits(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Resu
                         let name = captures[1].trim().to_string();
                         let description = captures[2].trim().to_string();
 
+                        let whole = captures.get(0).unwrap().as_str();
+                        let span = section.content.find(whole)
+                            .map(|start| SourceSpan { section_index, start, end: start + whole.len() });
+
                         // Create benefit entity
-                        let benefit_entity = Entity {
+                        let mut benefit_entity = Entity {
                             entity_type: "Benefit".to_string(),
                             name,
                             description: Some(description),
                             related: Vec::new(),
                             attributes: HashMap::new(),
+                            span,
                         };
+                        let source_text = format!("{} {}", benefit_entity.name, benefit_entity.description.clone().unwrap_or_default());
+                        tag_obligation(&mut benefit_entity, &source_text);
 
                         entities.push(benefit_entity);
                     }
@@ -256,13 +361,28 @@ fn extract_benefits(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Resu
                 for sentence in sentences {
                     if benefit_keywords.iter().any(|&kw| sentence.to_lowercase().contains(kw)) {
                         // Create benefit entity from sentence
-                        let benefit_entity = Entity {
+                        let span = section.content.find(sentence)
+                            .map(|start| SourceSpan { section_index, start, end: start + sentence.len() });
+
+                        let mut benefit_entity = Entity {
                             entity_type: "Benefit".to_string(),
                             name: sentence.to_string(),
                             description: None,
                             related: Vec::new(),
                             attributes: HashMap::new(),
+                            span: span.clone(),
                         };
+                        tag_obligation(&mut benefit_entity, sentence);
+
+                        diagnostics.push(Diagnostic {
+                            severity: DiagnosticSeverity::Warning,
+                            span,
+                            code: "fallback_sentence_extraction".to_string(),
+                            message: format!(
+                                "Benefit '{}' failed structured bullet/list parsing and fell back to whole-sentence extraction",
+                                benefit_entity.name
+                            ),
+                        });
 
                         entities.push(benefit_entity);
                     }
@@ -275,14 +395,14 @@ fn extract_benefits(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Resu
 }
 
 /// Extract exclusions from the document
-fn extract_exclusions(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Result<()> {
+fn extract_exclusions(doc: &ProcessedDocument, entities: &mut Vec<Entity>, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
     // Keywords that indicate exclusion sections
     let exclusion_keywords = [
         "exclusion", "exclusions", "excluded", "not covered", "not include", "limitation", "limitations"
     ];
 
     // Find sections related to exclusions
-    for section in &doc.sections {
+    for (section_index, section) in doc.sections.iter().enumerate() {
         let section_text = if let Some(title) = &section.title {
             format!("{} {}", title, section.content)
         } else {
@@ -305,14 +425,21 @@ fn extract_exclusions(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Re
                         let name = captures[1].trim().to_string();
                         let description = captures[2].trim().to_string();
 
+                        let whole = captures.get(0).unwrap().as_str();
+                        let span = section.content.find(whole)
+                            .map(|start| SourceSpan { section_index, start, end: start + whole.len() });
+
                         // Create exclusion entity
-                        let exclusion_entity = Entity {
+                        let mut exclusion_entity = Entity {
                             entity_type: "Exclusion".to_string(),
                             name,
                             description: Some(description),
                             related: Vec::new(),
                             attributes: HashMap::new(),
+                            span,
                         };
+                        let source_text = format!("{} {}", exclusion_entity.name, exclusion_entity.description.clone().unwrap_or_default());
+                        tag_obligation(&mut exclusion_entity, &source_text);
 
                         entities.push(exclusion_entity);
                     }
@@ -331,13 +458,28 @@ fn extract_exclusions(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Re
                 for sentence in sentences {
                     if exclusion_keywords.iter().any(|&kw| sentence.to_lowercase().contains(kw)) {
                         // Create exclusion entity from sentence
-                        let exclusion_entity = Entity {
+                        let span = section.content.find(sentence)
+                            .map(|start| SourceSpan { section_index, start, end: start + sentence.len() });
+
+                        let mut exclusion_entity = Entity {
                             entity_type: "Exclusion".to_string(),
                             name: sentence.to_string(),
                             description: None,
                             related: Vec::new(),
                             attributes: HashMap::new(),
+                            span: span.clone(),
                         };
+                        tag_obligation(&mut exclusion_entity, sentence);
+
+                        diagnostics.push(Diagnostic {
+                            severity: DiagnosticSeverity::Warning,
+                            span,
+                            code: "fallback_sentence_extraction".to_string(),
+                            message: format!(
+                                "Exclusion '{}' failed structured bullet/list parsing and fell back to whole-sentence extraction",
+                                exclusion_entity.name
+                            ),
+                        });
 
                         entities.push(exclusion_entity);
                     }
@@ -363,17 +505,25 @@ fn extract_procedures(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Re
         "ambulance", "hospitalization", "inpatient", "outpatient", "preventive care"
     ];
 
-    // Build Aho-Corasick automaton for efficient string matching
-    let ac = AhoCorasick::new(procedures).unwrap();
+    // Build Aho-Corasick automaton for efficient string matching. Leftmost-longest match kind so
+    // that compound names like "physical therapy" win over the shorter "physical"/"therapy"
+    // entries that also appear in the dictionary.
+    let ac = AhoCorasickBuilder::new()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(procedures)
+        .unwrap();
 
     // Find procedures in all sections
-    for section in &doc.sections {
+    for (section_index, section) in doc.sections.iter().enumerate() {
         let section_text = if let Some(title) = &section.title {
             format!("{} {}", title, section.content)
         } else {
             section.content.clone()
         };
 
+        // Match offsets are into `section_text`, which prefixes the content with "<title> "
+        let content_offset = section.title.as_ref().map_or(0, |title| title.len() + 1);
+
         // Find all matches
         let matches: Vec<_> = ac.find_iter(&section_text.to_lowercase()).collect();
 
@@ -387,6 +537,12 @@ fn extract_procedures(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Re
                 description: None,
                 related: Vec::new(),
                 attributes: HashMap::new(),
+                // Matches inside the title have no location within the content
+                span: mat.start().checked_sub(content_offset).map(|start| SourceSpan {
+                    section_index,
+                    start,
+                    end: mat.end() - content_offset,
+                }),
             };
 
             entities.push(procedure_entity);
@@ -396,6 +552,27 @@ fn extract_procedures(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Re
     Ok(())
 }
 
+/// Surface the document's RFC 2119 obligation scan (`ProcessedDocument::requirements`) as
+/// `Requirement` entities, so it flows through the same JSON/CSV/pivot output path as every
+/// other extracted entity instead of being stranded on `ProcessedDocument`
+fn extract_document_requirements(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Result<()> {
+    for requirement in &doc.requirements {
+        let mut attributes = HashMap::new();
+        attributes.insert("severity".to_string(), requirement.severity.to_string());
+
+        entities.push(Entity {
+            entity_type: "Requirement".to_string(),
+            name: requirement.sentence.clone(),
+            description: requirement.section_title.clone(),
+            related: Vec::new(),
+            attributes,
+            span: None,
+        });
+    }
+
+    Ok(())
+}
+
 /// Extract coverage information
 fn extract_coverage(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Result<()> {
     // Keywords that indicate coverage sections
@@ -428,6 +605,7 @@ fn extract_coverage(doc: &ProcessedDocument, entities: &mut Vec<Entity>) -> Resu
                 description: Some(section.content.clone()),
                 related: Vec::new(),
                 attributes: HashMap::new(),
+                span: None,
             };
 
             entities.push(coverage_entity);
@@ -467,13 +645,15 @@ fn extract_conditions_and_limitations(doc: &ProcessedDocument, entities: &mut Ve
                 "Condition".to_string()
             };
 
-            let condition_entity = Entity {
+            let mut condition_entity = Entity {
                 entity_type: "Condition".to_string(),
                 name: condition_name,
                 description: Some(section.content.clone()),
                 related: Vec::new(),
                 attributes: HashMap::new(),
+                span: None,
             };
+            tag_obligation(&mut condition_entity, &section.content);
 
             entities.push(condition_entity);
         }
@@ -487,13 +667,15 @@ fn extract_conditions_and_limitations(doc: &ProcessedDocument, entities: &mut Ve
                 "Limitation".to_string()
             };
 
-            let limitation_entity = Entity {
+            let mut limitation_entity = Entity {
                 entity_type: "Limitation".to_string(),
                 name: limitation_name,
                 description: Some(section.content.clone()),
                 related: Vec::new(),
                 attributes: HashMap::new(),
+                span: None,
             };
+            tag_obligation(&mut limitation_entity, &section.content);
 
             entities.push(limitation_entity);
         }
@@ -507,6 +689,82 @@ fn pattern_found_entities(patterns: &[Regex], text: &str) -> bool {
     patterns.iter().any(|pattern| pattern.is_match(text))
 }
 
+/// Annotate an entity's attributes with its obligation level and negation, if its source text
+/// contains a normative keyword. Reuses `document::classify_obligation` so the RFC 2119 keyword
+/// table is only maintained in one place.
+fn tag_obligation(entity: &mut Entity, text: &str) {
+    if let Some((level, negated)) = classify_obligation(text) {
+        entity.attributes.insert("obligation_level".to_string(), level.to_string());
+        if negated {
+            entity.attributes.insert("negated".to_string(), "true".to_string());
+        }
+    }
+}
+
+/// Detect conflicts between extracted entities: the same procedure classified as both a
+/// benefit and an exclusion, and a coverage entity whose text contains the full text of a
+/// `Must`-level limitation.
+fn find_conflicts(entities: &[Entity]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let benefits: Vec<&Entity> = entities.iter().filter(|e| e.entity_type == "Benefit").collect();
+    let exclusions: Vec<&Entity> = entities.iter().filter(|e| e.entity_type == "Exclusion").collect();
+
+    let benefit_names: HashSet<String> = benefits.iter().map(|e| e.name.to_lowercase()).collect();
+    let exclusion_names: HashSet<String> = exclusions.iter().map(|e| e.name.to_lowercase()).collect();
+
+    for name in benefit_names.intersection(&exclusion_names) {
+        let matching_benefits: Vec<&&Entity> = benefits.iter().filter(|e| e.name.to_lowercase() == *name).collect();
+        let matching_exclusions: Vec<&&Entity> = exclusions.iter().filter(|e| e.name.to_lowercase() == *name).collect();
+
+        // Keyword overlap (e.g. "covered" vs. "not covered") can extract the same literal
+        // sentence/span as both a Benefit and an Exclusion; that's a self-duplicate, not a
+        // genuine contradiction, so only flag a conflict when some pair has a distinct span.
+        let is_self_duplicate = matching_benefits.iter().all(|b| {
+            matching_exclusions.iter().all(|e| b.span.is_some() && b.span == e.span)
+        });
+        if is_self_duplicate {
+            continue;
+        }
+
+        // Trace back to whichever conflicting entity actually carries a source location
+        let span = matching_benefits.first().and_then(|e| e.span.clone())
+            .or_else(|| matching_exclusions.first().and_then(|e| e.span.clone()));
+
+        diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            span,
+            code: "conflicting_classification".to_string(),
+            message: format!("'{}' is classified as both a Benefit and an Exclusion", name),
+        });
+    }
+
+    for coverage in entities.iter().filter(|e| e.entity_type == "Coverage") {
+        let coverage_text = coverage.description.clone().unwrap_or_default().to_lowercase();
+
+        for limitation in entities.iter().filter(|e| {
+            e.entity_type == "Limitation"
+                && e.attributes.get("obligation_level").map(String::as_str) == Some("Must")
+        }) {
+            let limitation_text = limitation.description.clone().unwrap_or_default().to_lowercase();
+
+            if !limitation_text.is_empty() && coverage_text.contains(&limitation_text) {
+                diagnostics.push(Diagnostic {
+                    severity: DiagnosticSeverity::Warning,
+                    span: coverage.span.clone().or_else(|| limitation.span.clone()),
+                    code: "coverage_limitation_conflict".to_string(),
+                    message: format!(
+                        "Coverage '{}' text overlaps with Must-level Limitation '{}'",
+                        coverage.name, limitation.name
+                    ),
+                });
+            }
+        }
+    }
+
+    diagnostics
+}
+
 /// Deduplicate entities by name and type
 fn deduplicate_entities(entities: &mut Vec<Entity>) {
     let mut seen = HashSet::new();
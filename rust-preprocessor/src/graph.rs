@@ -0,0 +1,178 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::entities::Entity;
+
+/// Result of asking whether a procedure is covered
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoverageVerdict {
+    /// A path from the plan/policy root reaches the procedure without being blocked
+    Covered,
+
+    /// A path exists, but it is blocked by an exclusion or a `Must`-level limitation
+    NotCovered(BlockingEntity),
+
+    /// The procedure is not connected to the root at all (it isn't mentioned anywhere reachable)
+    Unknown,
+}
+
+/// The entity responsible for blocking coverage, with a human-readable reason
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockingEntity {
+    pub name: String,
+    pub entity_type: String,
+    pub reason: String,
+}
+
+/// A directed graph over an entity set, connecting the plan/policy root to the coverage,
+/// benefit and procedure entities it implies, and blocking edges from exclusions/limitations
+/// to the procedures or benefits they negate.
+pub struct EntityGraph {
+    entities: Vec<Entity>,
+    name_to_index: HashMap<String, usize>,
+    root: Option<usize>,
+    /// Edges that extend coverage reachability (root -> coverage -> benefit/procedure)
+    enables: Vec<Vec<usize>>,
+    /// Edges from an exclusion/limitation to the procedure or benefit it blocks
+    blocks: Vec<Vec<usize>>,
+}
+
+fn mentions(haystack: &str, needle: &str) -> bool {
+    !needle.trim().is_empty() && haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn is_blocking(entity: &Entity) -> bool {
+    entity.entity_type == "Exclusion"
+        || (entity.entity_type == "Limitation"
+            && entity.attributes.get("obligation_level").map(String::as_str) == Some("Must"))
+}
+
+impl EntityGraph {
+    /// Build a graph over `entities`, inferring edges from entity type and text containment
+    pub fn build(entities: &[Entity]) -> Self {
+        let entities = entities.to_vec();
+        let name_to_index: HashMap<String, usize> = entities
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.name.clone(), i))
+            .collect();
+
+        let root = entities
+            .iter()
+            .position(|e| e.entity_type == "Plan" || e.entity_type == "Policy");
+
+        let mut enables: Vec<Vec<usize>> = vec![Vec::new(); entities.len()];
+        let mut blocks: Vec<Vec<usize>> = vec![Vec::new(); entities.len()];
+
+        if let Some(root_idx) = root {
+            for (i, entity) in entities.iter().enumerate() {
+                if entity.entity_type == "Coverage" {
+                    enables[root_idx].push(i);
+                }
+            }
+        }
+
+        for (i, coverage) in entities.iter().enumerate() {
+            if coverage.entity_type != "Coverage" {
+                continue;
+            }
+            let coverage_text = coverage.description.clone().unwrap_or_default();
+
+            for (j, target) in entities.iter().enumerate() {
+                if (target.entity_type == "Benefit" || target.entity_type == "Procedure")
+                    && mentions(&coverage_text, &target.name)
+                {
+                    enables[i].push(j);
+                }
+            }
+        }
+
+        for (i, blocker) in entities.iter().enumerate() {
+            if !is_blocking(blocker) {
+                continue;
+            }
+            let blocker_text = format!("{} {}", blocker.name, blocker.description.clone().unwrap_or_default());
+
+            for (j, target) in entities.iter().enumerate() {
+                if (target.entity_type == "Benefit" || target.entity_type == "Procedure")
+                    && mentions(&blocker_text, &target.name)
+                {
+                    blocks[i].push(j);
+                }
+            }
+        }
+
+        EntityGraph { entities, name_to_index, root, enables, blocks }
+    }
+
+    /// Determine whether `procedure` is covered by running a BFS from the plan/policy root
+    /// over `enables` edges, then checking whether any node on the discovered path is blocked
+    /// by an exclusion or a `Must`-level limitation.
+    pub fn is_covered(&self, procedure: &str) -> CoverageVerdict {
+        let Some(root) = self.root else {
+            return CoverageVerdict::Unknown;
+        };
+
+        // Benefit/Exclusion entities are often named after a whole section title or sentence
+        // rather than the bare procedure term, so fall back from an exact name match to a
+        // case-insensitive exact match, and finally to a substring match against entity names.
+        let target = self.name_to_index.get(procedure).copied()
+            .or_else(|| self.entities.iter().position(|e| e.name.eq_ignore_ascii_case(procedure)))
+            .or_else(|| self.entities.iter().position(|e| mentions(&e.name, procedure)));
+
+        let Some(target) = target else {
+            return CoverageVerdict::Unknown;
+        };
+
+        // BFS from root to target over `enables` edges, tracking the path taken
+        let mut visited = vec![false; self.entities.len()];
+        let mut parent: Vec<Option<usize>> = vec![None; self.entities.len()];
+        let mut queue = VecDeque::new();
+
+        visited[root] = true;
+        queue.push_back(root);
+
+        while let Some(current) = queue.pop_front() {
+            if current == target {
+                break;
+            }
+
+            for &next in &self.enables[current] {
+                if !visited[next] {
+                    visited[next] = true;
+                    parent[next] = Some(current);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !visited[target] {
+            return CoverageVerdict::Unknown;
+        }
+
+        // Walk the discovered path back to root, looking for a blocked node
+        let mut path = vec![target];
+        let mut node = target;
+        while let Some(prev) = parent[node] {
+            path.push(prev);
+            node = prev;
+        }
+
+        for &node in &path {
+            for (blocker_idx, blocked_targets) in self.blocks.iter().enumerate() {
+                if blocked_targets.contains(&node) {
+                    let blocker = &self.entities[blocker_idx];
+                    return CoverageVerdict::NotCovered(BlockingEntity {
+                        name: blocker.name.clone(),
+                        entity_type: blocker.entity_type.clone(),
+                        reason: format!(
+                            "{} '{}' blocks coverage of '{}'",
+                            blocker.entity_type, blocker.name, self.entities[target].name
+                        ),
+                    });
+                }
+            }
+        }
+
+        CoverageVerdict::Covered
+    }
+}
@@ -1,11 +1,18 @@
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
-use log::{info, error};
+use log::{info, error, warn};
+use rayon::prelude::*;
 
+mod communities;
 mod document;
 mod entities;
+mod graph;
 mod output;
+mod rules;
+mod search;
+mod zoning;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -18,104 +25,388 @@ struct Cli {
 enum Commands {
     /// Process a document and extract structured data
     Process {
-        /// Input file path
+        /// Input file or directory path
         #[arg(short, long)]
         input: PathBuf,
-        
+
         /// Output file path
         #[arg(short, long)]
         output: PathBuf,
-        
-        /// Output format (json or csv)
+
+        /// Output format (json, csv, pivot, or graph). "graph" treats `output` as a directory
+        /// and writes nodes.csv/edges.csv/communities.csv into it instead of a single file.
         #[arg(short, long, default_value = "json")]
         format: String,
-        
+
         /// Document type (plan, policy, claim)
         #[arg(short, long)]
         doc_type: Option<String>,
+
+        /// When input is a directory, descend into subdirectories too
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// Optional path to a zoning config (JSON) that drives section classification
+        /// instead of the built-in keyword heuristics
+        #[arg(short, long)]
+        zoning_config: Option<PathBuf>,
+
+        /// Optional path to a matcher-rule config (JSON) that adds declarative, per-doc-type
+        /// entity extraction rules (exact/regex/literal-set/numeric) on top of the built-in ones
+        #[arg(short = 'c', long)]
+        rule_config: Option<PathBuf>,
+    },
+
+    /// Query a previously processed corpus using its persisted search index
+    Search {
+        /// Path to the index file (written by `process` as `<output>.index.json`)
+        #[arg(short, long)]
+        index: PathBuf,
+
+        /// Query text
+        #[arg(short, long)]
+        query: String,
+
+        /// Number of results to return
+        #[arg(short, long, default_value_t = 10)]
+        top_k: usize,
+    },
+
+    /// Check whether a procedure or benefit is covered, by building the entity graph from a
+    /// freshly processed document and running a coverage-reachability query over it
+    Coverage {
+        /// Input file path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Document type (plan, policy, claim)
+        #[arg(short, long)]
+        doc_type: Option<String>,
+
+        /// Optional path to a zoning config (JSON) that drives section classification
+        /// instead of the built-in keyword heuristics
+        #[arg(short, long)]
+        zoning_config: Option<PathBuf>,
+
+        /// Optional path to a matcher-rule config (JSON) that adds declarative, per-doc-type
+        /// entity extraction rules (exact/regex/literal-set/numeric) on top of the built-in ones
+        #[arg(short = 'c', long)]
+        rule_config: Option<PathBuf>,
+
+        /// Name of the procedure or benefit to check coverage for
+        #[arg(short, long)]
+        procedure: String,
     },
 }
 
 fn main() -> Result<()> {
     // Initialize logger
     env_logger::init();
-    
+
     // Parse command line arguments
     let cli = Cli::parse();
-    
+
     // Match command
     match &cli.command {
-        Commands::Process { input, output, format, doc_type } => {
-            process_document(input, output, format, doc_type.as_deref())
+        Commands::Process { input, output, format, doc_type, recursive, zoning_config, rule_config } => {
+            let matchers = match zoning_config {
+                Some(path) => Some(zoning::load_section_matchers(path)
+                    .with_context(|| format!("Failed to load zoning config: {:?}", path))?),
+                None => None,
+            };
+
+            let rule_sets = match rule_config {
+                Some(path) => Some(rules::load_rule_sets(path)
+                    .with_context(|| format!("Failed to load rule config: {:?}", path))?),
+                None => None,
+            };
+
+            if input.is_dir() {
+                process_directory(input, output, format, doc_type.as_deref(), *recursive, matchers.as_deref(), rule_sets.as_ref())
+            } else {
+                process_document(input, output, format, doc_type.as_deref(), matchers.as_deref(), rule_sets.as_ref())
+            }
+        }
+        Commands::Search { index, query, top_k } => run_search(index, query, *top_k),
+        Commands::Coverage { input, doc_type, zoning_config, rule_config, procedure } => {
+            let matchers = match zoning_config {
+                Some(path) => Some(zoning::load_section_matchers(path)
+                    .with_context(|| format!("Failed to load zoning config: {:?}", path))?),
+                None => None,
+            };
+
+            let rule_sets = match rule_config {
+                Some(path) => Some(rules::load_rule_sets(path)
+                    .with_context(|| format!("Failed to load rule config: {:?}", path))?),
+                None => None,
+            };
+
+            run_coverage(input, doc_type.as_deref(), matchers.as_deref(), rule_sets.as_ref(), procedure)
         }
     }
 }
 
-fn process_document(
+/// Path to the search index persisted alongside a given processed output file
+fn index_path_for(output: &Path) -> PathBuf {
+    let mut path = output.as_os_str().to_os_string();
+    path.push(".index.json");
+    PathBuf::from(path)
+}
+
+/// Load a persisted index and print the top matches for a query
+fn run_search(index_path: &PathBuf, query: &str, top_k: usize) -> Result<()> {
+    let index = search::InvertedIndex::load(index_path)
+        .with_context(|| format!("Failed to load search index from {:?}", index_path))?;
+
+    let results = index.search(query, top_k);
+
+    if results.is_empty() {
+        println!("No matches found for query: {}", query);
+    } else {
+        for (rank, (label, score)) in results.iter().enumerate() {
+            println!("{}. {} (score: {:.4})", rank + 1, label, score);
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the entity graph for a single document and print whether `procedure` is covered
+fn run_coverage(
     input: &PathBuf,
-    output: &PathBuf,
-    format: &str,
-    doc_type: Option<&str>
+    doc_type: Option<&str>,
+    zoning: Option<&[zoning::SectionMatcher]>,
+    rule_sets: Option<&HashMap<String, Vec<rules::MatchingRule>>>,
+    procedure: &str,
 ) -> Result<()> {
-    // Log start of processing
-    info!("Processing document: {:?}", input);
-    
-    // Check if input file exists
     if !input.exists() {
         error!("Input file does not exist: {:?}", input);
         anyhow::bail!("Input file does not exist");
     }
-    
-    // Determine document type if not provided
-    let doc_type = match doc_type {
-        Some(dt) => dt.to_string(),
-        None => {
-            // Try to infer from filename
-            let filename = input.file_name()
-                .and_then(|f| f.to_str())
-                .unwrap_or("");
-            
-            if filename.contains("plan") {
-                "plan".to_string()
-            } else if filename.contains("policy") {
-                "policy".to_string()
-            } else if filename.contains("claim") {
-                "claim".to_string()
-            } else {
-                // Default to generic
-                "generic".to_string()
-            }
+
+    let (_, entities, diagnostics) = process_single_file(input, doc_type, zoning, rule_sets)?;
+    log_diagnostics(input, &diagnostics);
+
+    let entity_graph = graph::EntityGraph::build(&entities);
+
+    match entity_graph.is_covered(procedure) {
+        graph::CoverageVerdict::Covered => {
+            println!("'{}' is covered", procedure);
+        }
+        graph::CoverageVerdict::NotCovered(blocker) => {
+            println!("'{}' is not covered: {}", procedure, blocker.reason);
         }
-    };
-    
-    // Read and parse the document
+        graph::CoverageVerdict::Unknown => {
+            println!("'{}' could not be found in the document's entity graph", procedure);
+        }
+    }
+
+    Ok(())
+}
+
+/// Infer the document type from a filename when one isn't given explicitly
+fn infer_doc_type(path: &Path, doc_type: Option<&str>) -> String {
+    if let Some(dt) = doc_type {
+        return dt.to_string();
+    }
+
+    let filename = path.file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("");
+
+    if filename.contains("plan") {
+        "plan".to_string()
+    } else if filename.contains("policy") {
+        "policy".to_string()
+    } else if filename.contains("claim") {
+        "claim".to_string()
+    } else {
+        "generic".to_string()
+    }
+}
+
+/// Read, process and extract entities from a single input file
+fn process_single_file(
+    input: &Path,
+    doc_type: Option<&str>,
+    zoning: Option<&[zoning::SectionMatcher]>,
+    rule_sets: Option<&HashMap<String, Vec<rules::MatchingRule>>>,
+) -> Result<(document::ProcessedDocument, Vec<entities::Entity>, Vec<entities::Diagnostic>)> {
+    let doc_type = infer_doc_type(input, doc_type);
+
     let content = std::fs::read_to_string(input)
         .with_context(|| format!("Failed to read input file: {:?}", input))?;
-    
-    // Process the document based on its type
+
     let processed_data = document::process(&content, &doc_type)
         .with_context(|| "Failed to process document")?;
-    
-    // Extract entities
-    let entities = entities::extract(&processed_data, &doc_type)
+
+    let applicable_rules = rule_sets.and_then(|sets| sets.get(&doc_type)).map(|rules| rules.as_slice());
+
+    let (entities, diagnostics) = entities::extract_with_zoning(&processed_data, &doc_type, zoning, applicable_rules)
         .with_context(|| "Failed to extract entities")?;
-    
-    // Write output
+
+    Ok((processed_data, entities, diagnostics))
+}
+
+/// Log extraction diagnostics at the appropriate level, prefixed with the source file
+fn log_diagnostics(input: &Path, diagnostics: &[entities::Diagnostic]) {
+    for diagnostic in diagnostics {
+        match diagnostic.severity {
+            entities::DiagnosticSeverity::Warning => warn!("{:?}: [{}] {}", input, diagnostic.code, diagnostic.message),
+            entities::DiagnosticSeverity::Error => error!("{:?}: [{}] {}", input, diagnostic.code, diagnostic.message),
+        }
+    }
+}
+
+/// Write a set of entities using the requested output format
+fn write_entities(entities: &[entities::Entity], output: &PathBuf, format: &str) -> Result<()> {
     match format.to_lowercase().as_str() {
         "json" => {
-            output::write_json(&entities, output)
+            output::write_json(entities, output)
                 .with_context(|| format!("Failed to write JSON output to {:?}", output))?;
         },
         "csv" => {
-            output::write_csv(&entities, output)
+            output::write_csv(entities, output)
                 .with_context(|| format!("Failed to write CSV output to {:?}", output))?;
         },
+        "pivot" => {
+            output::write_pivot(entities, output)
+                .with_context(|| format!("Failed to write pivot output to {:?}", output))?;
+        },
+        "graph" => {
+            std::fs::create_dir_all(output)
+                .with_context(|| format!("Failed to create output directory: {:?}", output))?;
+            output::write_graph_format(entities, output)
+                .with_context(|| format!("Failed to write graph output to {:?}", output))?;
+        },
         _ => {
             error!("Unsupported output format: {}", format);
             anyhow::bail!("Unsupported output format: {}", format);
         }
     }
-    
+
+    Ok(())
+}
+
+fn process_document(
+    input: &PathBuf,
+    output: &PathBuf,
+    format: &str,
+    doc_type: Option<&str>,
+    zoning: Option<&[zoning::SectionMatcher]>,
+    rule_sets: Option<&HashMap<String, Vec<rules::MatchingRule>>>,
+) -> Result<()> {
+    // Log start of processing
+    info!("Processing document: {:?}", input);
+
+    // Check if input file exists
+    if !input.exists() {
+        error!("Input file does not exist: {:?}", input);
+        anyhow::bail!("Input file does not exist");
+    }
+
+    // Extract entities from the single file
+    let (processed_data, entities, diagnostics) = process_single_file(input, doc_type, zoning, rule_sets)?;
+    log_diagnostics(input, &diagnostics);
+
+    // Write output
+    write_entities(&entities, output, format)?;
+
+    // Build and persist a search index alongside the output
+    let index = search::build_index(&processed_data, &entities);
+    index.save(index_path_for(output))
+        .with_context(|| "Failed to persist search index")?;
+
     info!("Successfully processed document and wrote output to {:?}", output);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Walk a directory (optionally recursing into subdirectories) collecting candidate input files
+fn collect_input_files(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {:?}", dir))?;
+
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read directory entry in {:?}", dir))?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            if recursive {
+                collect_input_files(&path, recursive, files)?;
+            }
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Process every file in a directory, running extraction concurrently and merging the results
+fn process_directory(
+    input: &PathBuf,
+    output: &PathBuf,
+    format: &str,
+    doc_type: Option<&str>,
+    recursive: bool,
+    zoning: Option<&[zoning::SectionMatcher]>,
+    rule_sets: Option<&HashMap<String, Vec<rules::MatchingRule>>>,
+) -> Result<()> {
+    info!("Processing document directory: {:?}", input);
+
+    let mut files = Vec::new();
+    collect_input_files(input, recursive, &mut files)?;
+
+    info!("Found {} file(s) to process in {:?}", files.len(), input);
+
+    // Process files concurrently, each file independently of the others
+    type FileResult = Result<(document::ProcessedDocument, Vec<entities::Entity>, Vec<entities::Diagnostic>)>;
+    let results: Vec<(PathBuf, FileResult)> = files
+        .into_par_iter()
+        .map(|path| {
+            info!("Processing document: {:?}", path);
+            let result = process_single_file(&path, doc_type, zoning, rule_sets);
+            (path, result)
+        })
+        .collect();
+
+    let mut all_entities = Vec::new();
+    let mut indices = Vec::new();
+    let mut failures = Vec::new();
+    let mut succeeded = 0;
+
+    for (path, result) in results {
+        match result {
+            Ok((processed_data, mut file_entities, diagnostics)) => {
+                log_diagnostics(&path, &diagnostics);
+                indices.push(search::build_index(&processed_data, &file_entities));
+                all_entities.append(&mut file_entities);
+                succeeded += 1;
+            },
+            Err(err) => failures.push((path, err)),
+        }
+    }
+
+    if !failures.is_empty() {
+        error!("Failed to process {} of the input file(s):", failures.len());
+        for (path, err) in &failures {
+            error!("  {:?}: {:#}", path, err);
+        }
+    }
+
+    write_entities(&all_entities, output, format)?;
+
+    // Merge each file's index into a single corpus-wide index
+    let merged_index = search::merge(indices);
+    merged_index.save(index_path_for(output))
+        .with_context(|| "Failed to persist search index")?;
+
+    info!(
+        "Successfully processed directory and wrote output to {:?} ({} succeeded, {} failed)",
+        output,
+        succeeded,
+        failures.len()
+    );
+
+    Ok(())
+}
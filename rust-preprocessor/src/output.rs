@@ -1,4 +1,5 @@
 use anyhow::{Result, Context};
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::fs::File;
 use std::io::BufWriter;
@@ -6,6 +7,7 @@ use serde_json;
 use csv;
 use polars::prelude::*;
 
+use crate::communities;
 use crate::entities::Entity;
 
 /// Write entities to a JSON file
@@ -86,8 +88,53 @@ pub fn write_csv<P: AsRef<Path>>(entities: &[Entity], output_path: P) -> Result<
     Ok(())
 }
 
-/// Write entities to a graph-friendly format (nodes and edges)
+/// Write entities as a pivoted attribute matrix: one row per entity, one column per distinct
+/// attribute key across the whole input, with empty cells where an entity lacks that key.
+pub fn write_pivot<P: AsRef<Path>>(entities: &[Entity], output_path: P) -> Result<()> {
+    // Union of every attribute key seen across all entities, in a stable order
+    let attribute_keys: BTreeSet<&str> = entities
+        .iter()
+        .flat_map(|entity| entity.attributes.keys().map(|k| k.as_str()))
+        .collect();
+
+    let mut names = Vec::with_capacity(entities.len());
+    let mut entity_types = Vec::with_capacity(entities.len());
+    for entity in entities {
+        names.push(entity.name.clone());
+        entity_types.push(entity.entity_type.clone());
+    }
+
+    let mut columns = vec![
+        Series::new("entity_name", names),
+        Series::new("entity_type", entity_types),
+    ];
+
+    for key in &attribute_keys {
+        let column: Vec<String> = entities
+            .iter()
+            .map(|entity| entity.attributes.get(*key).cloned().unwrap_or_default())
+            .collect();
+        columns.push(Series::new(key, column));
+    }
+
+    let mut df = DataFrame::new(columns)
+        .with_context(|| "Failed to create pivoted DataFrame")?;
+
+    let mut file = File::create(output_path.as_ref())
+        .with_context(|| format!("Failed to create output file: {:?}", output_path.as_ref()))?;
+
+    CsvWriter::new(&mut file)
+        .finish(&mut df)
+        .with_context(|| "Failed to write pivoted DataFrame to CSV")?;
+
+    Ok(())
+}
+
+/// Write entities to a graph-friendly format (nodes and edges), including detected communities
 pub fn write_graph_format<P: AsRef<Path>>(entities: &[Entity], output_dir: P) -> Result<()> {
+    // Detect communities via label propagation over the (undirected) `related` edges
+    let (labels, communities) = communities::detect_communities(entities);
+
     // Create nodes file
     let nodes_path = output_dir.as_ref().join("nodes.csv");
     let nodes_file = File::create(&nodes_path)
@@ -96,7 +143,7 @@ pub fn write_graph_format<P: AsRef<Path>>(entities: &[Entity], output_dir: P) ->
     let mut nodes_writer = csv::Writer::from_writer(nodes_file);
 
     // Write nodes header
-    nodes_writer.write_record(&["id", "label", "name", "description"])
+    nodes_writer.write_record(&["id", "label", "name", "description", "community_id"])
         .with_context(|| "Failed to write nodes CSV header")?;
 
     // Create edges file
@@ -118,6 +165,7 @@ pub fn write_graph_format<P: AsRef<Path>>(entities: &[Entity], output_dir: P) ->
             entity.entity_type.clone(),
             entity.name.clone(),
             entity.description.clone().unwrap_or_default(),
+            labels[i].to_string(),
         ])
         .with_context(|| format!("Failed to write node record for entity: {}", entity.name))?;
 
@@ -164,5 +212,27 @@ pub fn write_graph_format<P: AsRef<Path>>(entities: &[Entity], output_dir: P) ->
     edges_writer.flush()
         .with_context(|| "Failed to flush edges CSV writer")?;
 
+    // Write communities file listing each community's members
+    let communities_path = output_dir.as_ref().join("communities.csv");
+    let communities_file = File::create(&communities_path)
+        .with_context(|| format!("Failed to create communities file: {:?}", communities_path))?;
+
+    let mut communities_writer = csv::Writer::from_writer(communities_file);
+
+    communities_writer.write_record(&["community_id", "size", "members"])
+        .with_context(|| "Failed to write communities CSV header")?;
+
+    for community in &communities {
+        communities_writer.write_record(&[
+            community.id.to_string(),
+            community.members.len().to_string(),
+            community.members.join(";"),
+        ])
+        .with_context(|| format!("Failed to write community record for community: {}", community.id))?;
+    }
+
+    communities_writer.flush()
+        .with_context(|| "Failed to flush communities CSV writer")?;
+
     Ok(())
 }
@@ -0,0 +1,273 @@
+use anyhow::{Result, Context};
+use aho_corasick::AhoCorasickBuilder;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::document::ProcessedDocument;
+use crate::entities::{Entity, SourceSpan};
+
+/// How a rule locates candidate matches in section text
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// Match `pattern` as a literal, case-insensitive substring
+    Exact,
+    /// Match `pattern` as a regex, mapping captures to fields via `fields`
+    Regex,
+    /// Match any of `literals` via Aho-Corasick
+    LiteralSet,
+    /// Match `pattern` as a regex whose captured value is a numeric quantity (a dollar amount,
+    /// a percentage, a day count) to be parsed and normalized rather than kept as raw text
+    Numeric,
+}
+
+/// Which capture group (for `Regex`/`Numeric` rules) feeds which entity field
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct FieldMapping {
+    pub name_group: Option<usize>,
+    pub description_group: Option<usize>,
+    pub value_group: Option<usize>,
+}
+
+fn default_attribute() -> String {
+    "value".to_string()
+}
+
+/// A single declarative extraction rule: where to look (`kind`/`pattern`/`literals`), how to
+/// map captures onto entity fields (`fields`), and how to normalize a captured value
+/// (`normalize`) before storing it under `attribute`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MatchingRule {
+    /// Entity type this rule produces, e.g. "Benefit" or "CoverageLimit"
+    pub entity_type: String,
+
+    /// How to locate matches in the section text
+    pub kind: MatchKind,
+
+    /// Regex or exact-match pattern; unused when `kind` is `LiteralSet`
+    #[serde(default)]
+    pub pattern: String,
+
+    /// Literal strings to match against; used only when `kind` is `LiteralSet`
+    #[serde(default)]
+    pub literals: Vec<String>,
+
+    #[serde(default)]
+    pub fields: FieldMapping,
+
+    /// Name of a built-in normalizer applied to the captured value, e.g. "currency",
+    /// "percentage", "days", or "canonical_procedure"
+    pub normalize: Option<String>,
+
+    /// Attribute name the normalized value is stored under by `Regex` and `Numeric` rules;
+    /// `LiteralSet` rules use the normalized value as the entity name instead
+    #[serde(default = "default_attribute")]
+    pub attribute: String,
+}
+
+/// Rule definitions as loaded from a JSON file, grouped by `doc_type`
+#[derive(Debug, Deserialize)]
+struct RuleConfig {
+    rules: HashMap<String, Vec<MatchingRule>>,
+}
+
+/// Load a rule configuration file, keyed by `doc_type`
+pub fn load_rule_sets<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Vec<MatchingRule>>> {
+    let raw = std::fs::read_to_string(path.as_ref())
+        .with_context(|| format!("Failed to read rule config: {:?}", path.as_ref()))?;
+
+    let config: RuleConfig = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse rule config: {:?}", path.as_ref()))?;
+
+    Ok(config.rules)
+}
+
+/// Pull the leading numeric expression out of a captured value, e.g. "$1,500.00" -> 1500.0,
+/// "20%" -> 20.0, "30 days" -> 30.0
+fn normalize_numeric(raw: &str) -> Option<f64> {
+    static NUMBER: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let number = NUMBER.get_or_init(|| Regex::new(r"\d[\d,]*(?:\.\d+)?").unwrap());
+
+    let cleaned = number.find(raw)?.as_str().replace(',', "");
+    cleaned.parse::<f64>().ok()
+}
+
+/// Canonical names for procedures that commonly appear abbreviated
+fn canonical_procedure_name(raw: &str) -> String {
+    const ALIASES: &[(&str, &str)] = &[
+        ("pt", "physical therapy"),
+        ("ot", "occupational therapy"),
+        ("er", "emergency room"),
+        ("ct", "ct scan"),
+    ];
+
+    let lower = raw.trim().to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(lower)
+}
+
+/// Normalize a raw captured value using the named built-in normalizer, falling back to the
+/// trimmed raw text when the normalizer doesn't recognize a numeric quantity
+fn normalize_value(normalize: &str, raw: &str) -> String {
+    match normalize {
+        "currency" | "percentage" | "days" => normalize_numeric(raw)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| raw.trim().to_string()),
+        "canonical_procedure" => canonical_procedure_name(raw),
+        _ => raw.trim().to_string(),
+    }
+}
+
+/// Apply a document type's configured rules against every section, appending any matched
+/// entities. This is the config-driven counterpart to the hardcoded `extract_*` functions:
+/// adding a new entity kind becomes a rule definition rather than a new function.
+pub fn apply_rules(doc: &ProcessedDocument, rules: &[MatchingRule], entities: &mut Vec<Entity>) -> Result<()> {
+    for rule in rules {
+        match rule.kind {
+            MatchKind::Exact => apply_exact_rule(doc, rule, entities),
+            MatchKind::Regex => apply_regex_rule(doc, rule, entities)?,
+            MatchKind::LiteralSet => apply_literal_set_rule(doc, rule, entities)?,
+            MatchKind::Numeric => apply_numeric_rule(doc, rule, entities)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_exact_rule(doc: &ProcessedDocument, rule: &MatchingRule, entities: &mut Vec<Entity>) {
+    let needle = rule.pattern.to_lowercase();
+
+    for (section_index, section) in doc.sections.iter().enumerate() {
+        if let Some(start) = section.content.to_lowercase().find(&needle) {
+            entities.push(Entity {
+                entity_type: rule.entity_type.clone(),
+                name: rule.pattern.clone(),
+                description: Some(section.content.clone()),
+                related: Vec::new(),
+                attributes: HashMap::new(),
+                span: Some(SourceSpan { section_index, start, end: start + rule.pattern.len() }),
+            });
+        }
+    }
+}
+
+fn apply_regex_rule(doc: &ProcessedDocument, rule: &MatchingRule, entities: &mut Vec<Entity>) -> Result<()> {
+    let regex = Regex::new(&rule.pattern)
+        .with_context(|| format!("Failed to compile rule pattern for entity type '{}': {}", rule.entity_type, rule.pattern))?;
+
+    for (section_index, section) in doc.sections.iter().enumerate() {
+        for captures in regex.captures_iter(&section.content) {
+            let name = rule.fields.name_group
+                .and_then(|g| captures.get(g))
+                .map(|m| m.as_str().trim().to_string())
+                .unwrap_or_else(|| rule.entity_type.clone());
+
+            let description = rule.fields.description_group
+                .and_then(|g| captures.get(g))
+                .map(|m| m.as_str().trim().to_string());
+
+            let mut attributes = HashMap::new();
+            if let Some(value_match) = rule.fields.value_group.and_then(|g| captures.get(g)) {
+                let value = match &rule.normalize {
+                    Some(normalize) => normalize_value(normalize, value_match.as_str()),
+                    None => value_match.as_str().trim().to_string(),
+                };
+                attributes.insert(rule.attribute.clone(), value);
+            }
+
+            let whole = captures.get(0).unwrap();
+            entities.push(Entity {
+                entity_type: rule.entity_type.clone(),
+                name,
+                description,
+                related: Vec::new(),
+                attributes,
+                span: Some(SourceSpan { section_index, start: whole.start(), end: whole.end() }),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_literal_set_rule(doc: &ProcessedDocument, rule: &MatchingRule, entities: &mut Vec<Entity>) -> Result<()> {
+    if rule.literals.is_empty() {
+        return Ok(());
+    }
+
+    let ac = AhoCorasickBuilder::new()
+        .ascii_case_insensitive(true)
+        .build(&rule.literals)
+        .with_context(|| format!("Failed to build literal matcher for entity type '{}'", rule.entity_type))?;
+
+    for (section_index, section) in doc.sections.iter().enumerate() {
+        for mat in ac.find_iter(&section.content) {
+            let raw = &rule.literals[mat.pattern()];
+            let name = match &rule.normalize {
+                Some(normalize) => normalize_value(normalize, raw),
+                None => raw.clone(),
+            };
+
+            entities.push(Entity {
+                entity_type: rule.entity_type.clone(),
+                name,
+                description: None,
+                related: Vec::new(),
+                attributes: HashMap::new(),
+                span: Some(SourceSpan { section_index, start: mat.start(), end: mat.end() }),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// A `Numeric` rule behaves like a `Regex` rule, but always normalizes its captured value
+/// (defaulting to the "currency" normalizer and capture group 1) so that dollar limits, copay
+/// percentages and waiting-period day counts land in `attributes` as parsed numbers rather than
+/// being skipped by the keyword-only extractors.
+fn apply_numeric_rule(doc: &ProcessedDocument, rule: &MatchingRule, entities: &mut Vec<Entity>) -> Result<()> {
+    let regex = Regex::new(&rule.pattern)
+        .with_context(|| format!("Failed to compile rule pattern for entity type '{}': {}", rule.entity_type, rule.pattern))?;
+    let value_group = rule.fields.value_group.unwrap_or(1);
+    let normalize = rule.normalize.as_deref().unwrap_or("currency");
+
+    for (section_index, section) in doc.sections.iter().enumerate() {
+        for captures in regex.captures_iter(&section.content) {
+            let Some(value_match) = captures.get(value_group) else {
+                continue;
+            };
+            let value = normalize_value(normalize, value_match.as_str());
+
+            let name = rule.fields.name_group
+                .and_then(|g| captures.get(g))
+                .map(|m| m.as_str().trim().to_string())
+                .unwrap_or_else(|| format!("{} {}", rule.entity_type, value));
+
+            let description = rule.fields.description_group
+                .and_then(|g| captures.get(g))
+                .map(|m| m.as_str().trim().to_string());
+
+            let mut attributes = HashMap::new();
+            attributes.insert(rule.attribute.clone(), value);
+
+            let whole = captures.get(0).unwrap();
+            entities.push(Entity {
+                entity_type: rule.entity_type.clone(),
+                name,
+                description,
+                related: Vec::new(),
+                attributes,
+                span: Some(SourceSpan { section_index, start: whole.start(), end: whole.end() }),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use serde_json;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::document::ProcessedDocument;
+use crate::entities::Entity;
+
+/// BM25 tuning parameter controlling term-frequency saturation
+const K1: f64 = 1.2;
+
+/// BM25 tuning parameter controlling document-length normalization
+const B: f64 = 0.75;
+
+/// A small stopword list dropped during tokenization
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is",
+    "it", "of", "on", "or", "that", "the", "this", "to", "with",
+];
+
+/// A term's entry for one indexed document, with how often the term occurs there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Posting {
+    pub doc_id: usize,
+    pub term_frequency: usize,
+}
+
+/// Metadata about one indexed unit (a section or an entity)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedDoc {
+    /// Human-readable label identifying the source, e.g. "section:2" or "entity:Dental Cleaning"
+    pub label: String,
+
+    /// Number of (non-stopword) tokens in the indexed text
+    pub length: usize,
+}
+
+/// An in-memory inverted index over processed sections and extracted entities
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InvertedIndex {
+    /// Term -> postings list
+    pub postings: HashMap<String, Vec<Posting>>,
+
+    /// Indexed documents, referenced by `Posting::doc_id`
+    pub docs: Vec<IndexedDoc>,
+}
+
+/// Lowercase and split on non-alphanumeric boundaries, dropping stopwords and empty tokens
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|s| s.to_lowercase())
+        .filter(|s| !s.is_empty() && !STOPWORDS.contains(&s.as_str()))
+        .collect()
+}
+
+fn add_postings(postings: &mut HashMap<String, Vec<Posting>>, doc_id: usize, tokens: &[String]) {
+    let mut term_frequencies: HashMap<&str, usize> = HashMap::new();
+    for token in tokens {
+        *term_frequencies.entry(token.as_str()).or_insert(0) += 1;
+    }
+
+    for (term, term_frequency) in term_frequencies {
+        postings.entry(term.to_string()).or_default().push(Posting { doc_id, term_frequency });
+    }
+}
+
+/// Build an inverted index over a document's sections and its extracted entities
+pub fn build_index(doc: &ProcessedDocument, entities: &[Entity]) -> InvertedIndex {
+    let mut docs = Vec::new();
+    let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+
+    for (i, section) in doc.sections.iter().enumerate() {
+        let text = match &section.title {
+            Some(title) => format!("{} {}", title, section.content),
+            None => section.content.clone(),
+        };
+
+        let tokens = tokenize(&text);
+        let doc_id = docs.len();
+        docs.push(IndexedDoc { label: format!("section:{}", i), length: tokens.len() });
+        add_postings(&mut postings, doc_id, &tokens);
+    }
+
+    for entity in entities {
+        let text = match &entity.description {
+            Some(description) => format!("{} {}", entity.name, description),
+            None => entity.name.clone(),
+        };
+
+        let tokens = tokenize(&text);
+        let doc_id = docs.len();
+        docs.push(IndexedDoc { label: format!("entity:{}", entity.name), length: tokens.len() });
+        add_postings(&mut postings, doc_id, &tokens);
+    }
+
+    InvertedIndex { postings, docs }
+}
+
+/// Merge several indices (e.g. one per input file) into a single index, renumbering doc ids
+pub fn merge(indices: Vec<InvertedIndex>) -> InvertedIndex {
+    let mut docs = Vec::new();
+    let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+
+    for index in indices {
+        let offset = docs.len();
+        docs.extend(index.docs);
+
+        for (term, term_postings) in index.postings {
+            let entry = postings.entry(term).or_default();
+            for posting in term_postings {
+                entry.push(Posting {
+                    doc_id: posting.doc_id + offset,
+                    term_frequency: posting.term_frequency,
+                });
+            }
+        }
+    }
+
+    InvertedIndex { postings, docs }
+}
+
+impl InvertedIndex {
+    /// Rank indexed documents against `query` using Okapi BM25, returning the top `top_k` labels
+    pub fn search(&self, query: &str, top_k: usize) -> Vec<(String, f64)> {
+        if self.docs.is_empty() {
+            return Vec::new();
+        }
+
+        let query_terms = tokenize(query);
+        let n = self.docs.len() as f64;
+        let avg_len = self.docs.iter().map(|d| d.length as f64).sum::<f64>() / n;
+
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for term in &query_terms {
+            let Some(term_postings) = self.postings.get(term) else {
+                continue;
+            };
+
+            let df = term_postings.len() as f64;
+            let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+
+            for posting in term_postings {
+                let doc_len = self.docs[posting.doc_id].length as f64;
+                let tf = posting.term_frequency as f64;
+                let denom = tf + K1 * (1.0 - B + B * doc_len / avg_len.max(1.0));
+
+                *scores.entry(posting.doc_id).or_insert(0.0) += idf * (tf * (K1 + 1.0)) / denom;
+            }
+        }
+
+        let mut ranked: Vec<(usize, f64)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(top_k);
+
+        ranked
+            .into_iter()
+            .map(|(doc_id, score)| (self.docs[doc_id].label.clone(), score))
+            .collect()
+    }
+
+    /// Persist the index as JSON alongside the processed output
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = std::fs::File::create(path.as_ref())
+            .with_context(|| format!("Failed to create index file: {:?}", path.as_ref()))?;
+
+        serde_json::to_writer_pretty(file, self)
+            .with_context(|| "Failed to serialize inverted index")?;
+
+        Ok(())
+    }
+
+    /// Load a previously persisted index
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read index file: {:?}", path.as_ref()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to deserialize inverted index from {:?}", path.as_ref()))
+    }
+}
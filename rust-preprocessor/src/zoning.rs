@@ -0,0 +1,121 @@
+use anyhow::{Result, Context, bail};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Raw zoning configuration as loaded from a JSON definition file
+#[derive(Debug, Deserialize)]
+struct ZoningConfig {
+    /// Named regex fragments, which may reference other fragments by `{name}`
+    fragments: HashMap<String, String>,
+
+    /// Section heading definitions, each composed from fragments
+    sections: Vec<SectionDef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SectionDef {
+    /// Human-readable name for this section kind, e.g. "benefit_summary"
+    name: String,
+
+    /// Heading pattern, composed of literal regex plus `{fragment_name}` references
+    pattern: String,
+
+    /// Entity type this section should be classified as, e.g. "Benefit"
+    entity_type: String,
+}
+
+/// A resolved section heading matcher, ready to run against document text
+#[derive(Debug, Clone)]
+pub struct SectionMatcher {
+    pub name: String,
+    pub entity_type: String,
+    pub regex: Regex,
+}
+
+/// Resolve a single fragment by name, recursively expanding any fragment references it
+/// contains, and detecting reference cycles.
+fn resolve_fragment(
+    name: &str,
+    fragments: &HashMap<String, String>,
+    resolved: &mut HashMap<String, String>,
+    visiting: &mut HashSet<String>,
+) -> Result<String> {
+    if let Some(existing) = resolved.get(name) {
+        return Ok(existing.clone());
+    }
+
+    if !visiting.insert(name.to_string()) {
+        bail!("Cycle detected while resolving zoning fragment: {}", name);
+    }
+
+    let raw = fragments
+        .get(name)
+        .with_context(|| format!("Unknown zoning fragment: {}", name))?;
+
+    let expanded = expand_references(raw, fragments, resolved, visiting)?;
+
+    visiting.remove(name);
+    resolved.insert(name.to_string(), expanded.clone());
+
+    Ok(expanded)
+}
+
+/// Replace every `{fragment_name}` reference in `pattern` with its resolved expansion
+fn expand_references(
+    pattern: &str,
+    fragments: &HashMap<String, String>,
+    resolved: &mut HashMap<String, String>,
+    visiting: &mut HashSet<String>,
+) -> Result<String> {
+    static REFERENCE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    let reference = REFERENCE.get_or_init(|| Regex::new(r"\{(\w+)\}").unwrap());
+
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut last_end = 0;
+
+    for captures in reference.captures_iter(pattern) {
+        let whole = captures.get(0).unwrap();
+        let fragment_name = &captures[1];
+
+        expanded.push_str(&pattern[last_end..whole.start()]);
+        expanded.push_str(&resolve_fragment(fragment_name, fragments, resolved, visiting)?);
+        last_end = whole.end();
+    }
+
+    expanded.push_str(&pattern[last_end..]);
+    Ok(expanded)
+}
+
+/// Load a zoning configuration file and resolve it into ready-to-use section matchers
+pub fn load_section_matchers<P: AsRef<Path>>(path: P) -> Result<Vec<SectionMatcher>> {
+    let raw = std::fs::read_to_string(path.as_ref())
+        .with_context(|| format!("Failed to read zoning config: {:?}", path.as_ref()))?;
+
+    let config: ZoningConfig = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse zoning config: {:?}", path.as_ref()))?;
+
+    let mut resolved = HashMap::new();
+    for name in config.fragments.keys() {
+        resolve_fragment(name, &config.fragments, &mut resolved, &mut HashSet::new())?;
+    }
+
+    let mut matchers = Vec::with_capacity(config.sections.len());
+    for def in &config.sections {
+        let expanded = expand_references(&def.pattern, &config.fragments, &mut resolved, &mut HashSet::new())
+            .with_context(|| format!("Failed to expand pattern for section: {}", def.name))?;
+
+        let regex = Regex::new(&expanded)
+            .with_context(|| format!("Failed to compile pattern for section '{}': {}", def.name, expanded))?;
+
+        matchers.push(SectionMatcher {
+            name: def.name.clone(),
+            entity_type: def.entity_type.clone(),
+            regex,
+        });
+    }
+
+    Ok(matchers)
+}